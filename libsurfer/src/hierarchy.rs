@@ -2,7 +2,7 @@
 use crate::message::Message;
 use crate::transaction_container::StreamScopeRef;
 use crate::variable_filter::VariableFilter;
-use crate::wave_container::{ScopeRef, ScopeRefExt};
+use crate::wave_container::{ScopeRef, ScopeRefExt, WaveContainer};
 use crate::wave_data::ScopeType;
 use crate::SystemState;
 use derive_more::Display;
@@ -85,8 +85,11 @@ fn draw_variables(state: &mut SystemState, msgs: &mut Vec<Message>, ui: &mut Ui)
         match active_scope {
             ScopeType::WaveScope(scope) => {
                 let wave_container = waves.inner.as_waves().unwrap();
-                let variables =
-                    state.filtered_variables(&wave_container.variables_in_scope(scope), filter);
+                let variables = state.filtered_variables(
+                    wave_container,
+                    &wave_container.variables_in_scope(scope),
+                    filter,
+                );
                 // Parameters shown in variable list
                 if !state.show_parameters_in_scopes() {
                     let parameters = wave_container.parameters_in_scope(scope);
@@ -121,6 +124,7 @@ fn draw_variables(state: &mut SystemState, msgs: &mut Vec<Message>, ui: &mut Ui)
                                     msgs,
                                     wave_container,
                                     ui,
+                                    0,
                                     &variables,
                                 );
                             });
@@ -138,6 +142,7 @@ fn draw_variables(state: &mut SystemState, msgs: &mut Vec<Message>, ui: &mut Ui)
                             msgs,
                             wave_container,
                             ui,
+                            row_range.start,
                             &variables[row_range],
                         );
                     });
@@ -0,0 +1,105 @@
+//! Messages produced by the UI and handled by [`SystemState::update`].
+use crate::transaction_container::TransactionStreamRef;
+use crate::variable_filter::VariableNameFilterType;
+use crate::wave_container::{VariableDirection, VariableRef, VariableType};
+use crate::SystemState;
+
+pub enum Message {
+    AddVariables(Vec<VariableRef>),
+    AddStreamOrGenerator(TransactionStreamRef),
+    SetVariableNameFilterType(VariableNameFilterType),
+    SetVariableNameFilterCaseInsensitive(bool),
+    SetFilterFocused(bool),
+    SetVariableNameFilterMinWidth(Option<u32>),
+    SetVariableNameFilterMaxWidth(Option<u32>),
+    ToggleVariableNameFilterDirection(VariableDirection),
+    ToggleVariableNameFilterType(VariableType),
+    SaveVariableFilterPreset(String),
+    ApplyVariableFilterPreset(String),
+    DeleteVariableFilterPreset(String),
+    QuickOpenQueryChanged,
+    QuickOpenSelectNext,
+    QuickOpenSelectPrevious,
+    QuickOpenSelect(usize),
+    QuickOpenConfirm,
+}
+
+impl SystemState {
+    pub fn update(&mut self, message: Message) {
+        match message {
+            Message::AddVariables(vars) => {
+                if let Some(waves) = self.user.waves.as_mut() {
+                    waves.add_variables(vars);
+                }
+            }
+            Message::AddStreamOrGenerator(stream) => {
+                if let Some(waves) = self.user.waves.as_mut() {
+                    waves.add_stream_or_generator(stream);
+                }
+            }
+            Message::SetVariableNameFilterType(filter_type) => {
+                self.user.variable_filter.name_filter_type = filter_type;
+            }
+            Message::SetVariableNameFilterCaseInsensitive(case_insensitive) => {
+                self.user.variable_filter.name_filter_case_insensitive = case_insensitive;
+            }
+            Message::SetFilterFocused(focused) => {
+                self.user.filter_focused = focused;
+            }
+            Message::SetVariableNameFilterMinWidth(min_width) => {
+                self.user.variable_filter.structural_filter.min_width = min_width;
+            }
+            Message::SetVariableNameFilterMaxWidth(max_width) => {
+                self.user.variable_filter.structural_filter.max_width = max_width;
+            }
+            Message::ToggleVariableNameFilterDirection(direction) => {
+                let directions = &mut self.user.variable_filter.structural_filter.directions;
+                if let Some(pos) = directions.iter().position(|d| *d == direction) {
+                    directions.remove(pos);
+                } else {
+                    directions.push(direction);
+                }
+            }
+            Message::ToggleVariableNameFilterType(var_type) => {
+                let var_types = &mut self.user.variable_filter.structural_filter.var_types;
+                if let Some(pos) = var_types.iter().position(|t| *t == var_type) {
+                    var_types.remove(pos);
+                } else {
+                    var_types.push(var_type);
+                }
+            }
+            Message::SaveVariableFilterPreset(name) => self.save_variable_filter_preset(name),
+            Message::ApplyVariableFilterPreset(name) => self.apply_variable_filter_preset(&name),
+            Message::DeleteVariableFilterPreset(name) => self.delete_variable_filter_preset(&name),
+            Message::QuickOpenQueryChanged => self.recompute_quick_open_results(),
+            Message::QuickOpenSelectNext => {
+                let len = self.user.quick_open.results.len();
+                if len > 0 {
+                    self.user.quick_open.selected = (self.user.quick_open.selected + 1) % len;
+                }
+            }
+            Message::QuickOpenSelectPrevious => {
+                let len = self.user.quick_open.results.len();
+                if len > 0 {
+                    self.user.quick_open.selected =
+                        (self.user.quick_open.selected + len - 1) % len;
+                }
+            }
+            Message::QuickOpenSelect(idx) => {
+                self.user.quick_open.selected = idx;
+            }
+            Message::QuickOpenConfirm => {
+                if let Some(m) = self
+                    .user
+                    .quick_open
+                    .results
+                    .get(self.user.quick_open.selected)
+                {
+                    let var = m.var.clone();
+                    self.close_quick_open();
+                    self.update(Message::AddVariables(vec![var]));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,165 @@
+//! A global "quick open" palette that fuzzy-searches every variable in the design at once,
+//! regardless of which scope is currently active in the hierarchy panel.
+use egui::{
+    Align2, Area, Color32, Frame, Key, Modifiers, Order, RichText, ScrollArea, TextEdit, Ui, Vec2,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::message::Message;
+use crate::variable_filter::{VariableFilter, VariableMatch, VariableNameFilterType};
+use crate::wave_container::VariableRef;
+use crate::SystemState;
+
+const MAX_RESULTS: usize = 50;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct QuickOpen {
+    #[serde(skip)]
+    pub open: bool,
+    pub query: String,
+    #[serde(skip)]
+    pub results: Vec<VariableMatch>,
+    #[serde(skip)]
+    pub selected: usize,
+}
+
+impl QuickOpen {
+    pub fn new() -> QuickOpen {
+        QuickOpen::default()
+    }
+}
+
+impl SystemState {
+    /// Handles the global Ctrl+P shortcut and draws the overlay when open. Must be called
+    /// once per frame from the application's top-level update, independent of whether any
+    /// particular panel (e.g. the hierarchy/scopes panel) is shown that frame, so the
+    /// palette keeps working even when that panel is hidden or collapsed.
+    pub fn handle_global_quick_open(&mut self, ctx: &egui::Context, msgs: &mut Vec<Message>) {
+        if !self.user.quick_open.open
+            && ctx.input_mut(|i| i.consume_key(Modifiers::COMMAND, Key::P))
+        {
+            self.open_quick_open();
+        }
+        self.draw_quick_open(ctx, msgs);
+    }
+
+    pub fn open_quick_open(&mut self) {
+        self.user.quick_open.open = true;
+        self.user.quick_open.selected = 0;
+        self.recompute_quick_open_results();
+    }
+
+    pub fn close_quick_open(&mut self) {
+        self.user.quick_open.open = false;
+        self.user.quick_open.query.clear();
+        self.user.quick_open.results.clear();
+        self.user.quick_open.selected = 0;
+    }
+
+    /// Re-runs the quick-open query against every variable in the design, reusing
+    /// [`VariableFilter`] so ranking matches the in-panel fuzzy filter.
+    pub fn recompute_quick_open_results(&mut self) {
+        let Some(waves) = self.user.waves.as_ref() else {
+            self.user.quick_open.results.clear();
+            return;
+        };
+        let Some(wave_container) = waves.inner.as_waves() else {
+            self.user.quick_open.results.clear();
+            return;
+        };
+
+        let all_variables: Vec<VariableRef> = wave_container
+            .root_scopes()
+            .iter()
+            .flat_map(|scope| all_variables_in_scope_recursive(wave_container, scope))
+            .collect();
+
+        let filter = VariableFilter {
+            name_filter_type: VariableNameFilterType::Fuzzy,
+            name_filter_str: self.user.quick_open.query.clone(),
+            name_filter_case_insensitive: true,
+            structural_filter: Default::default(),
+        };
+
+        // Reuse `filtered_variables` rather than re-deriving its score/tie-break ordering
+        // here, so the palette and the in-panel filter can't drift apart again.
+        let mut results = self.filtered_variables(wave_container, &all_variables, &filter);
+        results.truncate(MAX_RESULTS);
+        self.user.quick_open.results = results;
+        self.user.quick_open.selected = 0;
+    }
+
+    fn draw_quick_open(&mut self, ctx: &egui::Context, msgs: &mut Vec<Message>) {
+        if !self.user.quick_open.open {
+            return;
+        }
+
+        let mut still_open = true;
+        Area::new("quick_open".into())
+            .order(Order::Foreground)
+            .anchor(Align2::CENTER_TOP, Vec2::new(0., 80.))
+            .show(ctx, |ui| {
+                Frame::popup(ui.style()).inner_margin(8.0).show(ui, |ui| {
+                    ui.set_min_width(420.0);
+                    let response = ui.add(
+                        TextEdit::singleline(&mut self.user.quick_open.query)
+                            .hint_text("Search all variables…")
+                            .desired_width(400.0),
+                    );
+                    response.request_focus();
+                    if response.changed() {
+                        msgs.push(Message::QuickOpenQueryChanged);
+                    }
+
+                    if ui.input(|i| i.key_pressed(Key::Escape)) {
+                        still_open = false;
+                    } else if ui.input(|i| i.key_pressed(Key::ArrowDown)) {
+                        msgs.push(Message::QuickOpenSelectNext);
+                    } else if ui.input(|i| i.key_pressed(Key::ArrowUp)) {
+                        msgs.push(Message::QuickOpenSelectPrevious);
+                    } else if ui.input(|i| i.key_pressed(Key::Enter)) {
+                        msgs.push(Message::QuickOpenConfirm);
+                    }
+
+                    ui.separator();
+                    ScrollArea::vertical().max_height(320.0).show(ui, |ui| {
+                        self.draw_quick_open_results(ui, msgs);
+                    });
+                });
+            });
+
+        if !still_open {
+            self.close_quick_open();
+        }
+    }
+
+    fn draw_quick_open_results(&self, ui: &mut Ui, msgs: &mut Vec<Message>) {
+        for (idx, m) in self.user.quick_open.results.iter().enumerate() {
+            let selected = idx == self.user.quick_open.selected;
+            let job = self.matched_variable_name_layout_job(m);
+            ui.horizontal(|ui| {
+                if ui.selectable_label(selected, job).clicked() {
+                    msgs.push(Message::QuickOpenSelect(idx));
+                    msgs.push(Message::QuickOpenConfirm);
+                }
+                ui.label(
+                    RichText::new(m.var.path.to_string())
+                        .weak()
+                        .color(Color32::GRAY),
+                );
+            });
+        }
+    }
+}
+
+/// Recursively collects every variable under `scope`, including nested scopes.
+fn all_variables_in_scope_recursive(
+    wave_container: &dyn crate::wave_container::WaveContainer,
+    scope: &crate::wave_container::ScopeRef,
+) -> Vec<VariableRef> {
+    let mut variables = wave_container.variables_in_scope(scope);
+    for child in wave_container.child_scopes(scope) {
+        variables.extend(all_variables_in_scope_recursive(wave_container, &child));
+    }
+    variables
+}
@@ -1,6 +1,7 @@
 //! Filtering of the variable list.
 use derive_more::Display;
-use egui::{Button, Layout, TextEdit, Ui};
+use egui::text::LayoutJob;
+use egui::{Button, Layout, TextEdit, TextFormat, Ui};
 use egui_remixicon::icons;
 use emath::{Align, Vec2};
 use enum_iterator::Sequence;
@@ -9,8 +10,10 @@ use itertools::Itertools;
 use regex::{escape, Regex, RegexBuilder};
 use serde::{Deserialize, Serialize};
 
+use crate::config::SurferTheme;
 use crate::data_container::DataContainer::Transactions;
 use crate::transaction_container::{StreamScopeRef, TransactionStreamRef};
+use crate::wave_container::{VariableDirection, VariableType, WaveContainer};
 use crate::wave_data::ScopeType;
 use crate::{message::Message, wave_container::VariableRef, SystemState};
 
@@ -29,11 +32,92 @@ pub enum VariableNameFilterType {
     Contain,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VariableFilter {
     pub(crate) name_filter_type: VariableNameFilterType,
     pub(crate) name_filter_str: String,
     pub(crate) name_filter_case_insensitive: bool,
+    /// ANDed with the name filter; empty/default is a no-op.
+    #[serde(default)]
+    pub(crate) structural_filter: StructuralFilter,
+}
+
+/// Structural constraints on top of the name filter; an empty/default filter is a no-op.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StructuralFilter {
+    pub min_width: Option<u32>,
+    pub max_width: Option<u32>,
+    pub var_types: Vec<VariableType>,
+    pub directions: Vec<VariableDirection>,
+}
+
+/// Metadata subset [`StructuralFilter`] matches against, decoupled from the wave
+/// container's type so the matching logic is unit-testable without a loaded wave file.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct StructuralMeta {
+    pub num_bits: Option<u32>,
+    pub variable_type: Option<VariableType>,
+    pub direction: Option<VariableDirection>,
+}
+
+impl StructuralFilter {
+    pub fn is_empty(&self) -> bool {
+        self.min_width.is_none()
+            && self.max_width.is_none()
+            && self.var_types.is_empty()
+            && self.directions.is_empty()
+    }
+
+    /// Checks `var` against the constraints, looking its metadata up in `wave_container`.
+    /// A variable whose metadata can't be looked up never matches a non-empty filter.
+    fn matches(&self, wave_container: &dyn WaveContainer, var: &VariableRef) -> bool {
+        if self.is_empty() {
+            return true;
+        }
+        let Ok(meta) = wave_container.variable_meta(var) else {
+            return false;
+        };
+        self.matches_meta(&StructuralMeta {
+            num_bits: meta.num_bits,
+            variable_type: meta.variable_type,
+            direction: meta.direction,
+        })
+    }
+
+    fn matches_meta(&self, meta: &StructuralMeta) -> bool {
+        if let Some(min_width) = self.min_width {
+            if meta.num_bits.map_or(true, |w| w < min_width) {
+                return false;
+            }
+        }
+        if let Some(max_width) = self.max_width {
+            if meta.num_bits.map_or(true, |w| w > max_width) {
+                return false;
+            }
+        }
+        if !self.var_types.is_empty()
+            && !meta
+                .variable_type
+                .is_some_and(|t| self.var_types.contains(&t))
+        {
+            return false;
+        }
+        if !self.directions.is_empty()
+            && !meta.direction.is_some_and(|d| self.directions.contains(&d))
+        {
+            return false;
+        }
+        true
+    }
+}
+
+/// A variable that matched a [`VariableFilter`], with the fuzzy matcher's score and
+/// matched character indices (empty/zero for non-fuzzy filter types and an empty query).
+#[derive(Debug, Clone)]
+pub struct VariableMatch {
+    pub var: VariableRef,
+    pub score: i64,
+    pub indices: Vec<usize>,
 }
 
 impl VariableFilter {
@@ -42,6 +126,7 @@ impl VariableFilter {
             name_filter_type: VariableNameFilterType::Contain,
             name_filter_str: String::from(""),
             name_filter_case_insensitive: true,
+            structural_filter: StructuralFilter::default(),
         }
     }
 
@@ -96,14 +181,57 @@ impl VariableFilter {
         }
     }
 
-    pub fn matching_variables(&self, variables: &[VariableRef]) -> Vec<VariableRef> {
-        let mut name_filter = self.name_filter_fn();
+    pub fn matching_variables(
+        &self,
+        wave_container: &dyn WaveContainer,
+        variables: &[VariableRef],
+    ) -> Vec<VariableMatch> {
+        // Fuzzy matching is scored, so it gets its own path that keeps the score and the
+        // matched character indices around for ranking and highlighting.
+        let matches = if self.name_filter_type == VariableNameFilterType::Fuzzy
+            && !self.name_filter_str.is_empty()
+        {
+            let matcher = if self.name_filter_case_insensitive {
+                SkimMatcherV2::default().ignore_case()
+            } else {
+                SkimMatcherV2::default().respect_case()
+            };
 
-        variables
-            .iter()
-            .filter(|&vr| name_filter(&vr.name))
-            .cloned()
-            .collect_vec()
+            variables
+                .iter()
+                .filter_map(|vr| {
+                    matcher.fuzzy_indices(&vr.name, &self.name_filter_str).map(
+                        |(score, indices)| VariableMatch {
+                            var: vr.clone(),
+                            score,
+                            indices,
+                        },
+                    )
+                })
+                .collect_vec()
+        } else {
+            let mut name_filter = self.name_filter_fn();
+
+            variables
+                .iter()
+                .filter(|&vr| name_filter(&vr.name))
+                .cloned()
+                .map(|var| VariableMatch {
+                    var,
+                    score: 0,
+                    indices: vec![],
+                })
+                .collect_vec()
+        };
+
+        if self.structural_filter.is_empty() {
+            matches
+        } else {
+            matches
+                .into_iter()
+                .filter(|m| self.structural_filter.matches(wave_container, &m.var))
+                .collect_vec()
+        }
     }
 }
 
@@ -126,15 +254,18 @@ impl SystemState {
                         if let Some(active_scope) = waves.active_scope.as_ref() {
                             match active_scope {
                                 ScopeType::WaveScope(active_scope) => {
-                                    let variables = waves
-                                        .inner
-                                        .as_waves()
-                                        .unwrap()
-                                        .variables_in_scope(active_scope);
-                                    msgs.push(Message::AddVariables(self.filtered_variables(
-                                        &variables,
-                                        &self.user.variable_filter,
-                                    )));
+                                    let wave_container = waves.inner.as_waves().unwrap();
+                                    let variables = wave_container.variables_in_scope(active_scope);
+                                    msgs.push(Message::AddVariables(
+                                        self.filtered_variables(
+                                            wave_container,
+                                            &variables,
+                                            &self.user.variable_filter,
+                                        )
+                                        .into_iter()
+                                        .map(|m| m.var)
+                                        .collect_vec(),
+                                    ));
                                 }
                                 ScopeType::StreamScope(active_scope) => {
                                     let Transactions(inner) = &waves.inner else {
@@ -190,6 +321,15 @@ impl SystemState {
                     msgs,
                     &self.user.variable_filter.name_filter_type,
                 );
+                ui.separator();
+                structural_filter_menu(ui, msgs, &self.user.variable_filter.structural_filter);
+                ui.separator();
+                variable_filter_presets_menu(
+                    ui,
+                    msgs,
+                    &self.user.variable_filter_presets,
+                    &mut self.user.variable_filter_new_preset_name,
+                );
             });
             ui.add_enabled(
                 !self.user.variable_filter.name_filter_str.is_empty(),
@@ -229,18 +369,142 @@ impl SystemState {
         });
     }
 
+    /// Variables matching `variable_filter`, ranked by score (ties by name) in Fuzzy mode
+    /// with a non-empty query, and alphanumerically otherwise.
     pub fn filtered_variables(
         &self,
+        wave_container: &dyn WaveContainer,
         variables: &[VariableRef],
         variable_filter: &VariableFilter,
-    ) -> Vec<VariableRef> {
-        variable_filter
-            .matching_variables(variables)
-            .iter()
-            .sorted_by(|a, b| numeric_sort::cmp(&a.name, &b.name))
-            .cloned()
-            .collect_vec()
+    ) -> Vec<VariableMatch> {
+        let matches = variable_filter.matching_variables(wave_container, variables);
+
+        if variable_filter.name_filter_type == VariableNameFilterType::Fuzzy
+            && !variable_filter.name_filter_str.is_empty()
+        {
+            matches
+                .into_iter()
+                .sorted_by(|a, b| {
+                    b.score
+                        .cmp(&a.score)
+                        .then_with(|| numeric_sort::cmp(&a.var.name, &b.var.name))
+                })
+                .collect_vec()
+        } else {
+            matches
+                .into_iter()
+                .sorted_by(|a, b| numeric_sort::cmp(&a.var.name, &b.var.name))
+                .collect_vec()
+        }
+    }
+
+    pub fn matched_variable_name_layout_job(&self, m: &VariableMatch) -> LayoutJob {
+        matched_name_layout_job(&m.var.name, &m.indices, &self.user.config.theme)
     }
+
+    /// Draws `matches`, a (possibly paginated) slice of a ranked/filtered variable list.
+    /// `start_index` is the absolute index of `matches[0]` in the full filtered list, so
+    /// zebra striping doesn't shift as the list is scrolled with `ScrollArea::show_rows`.
+    pub fn draw_filtered_variable_list(
+        &mut self,
+        msgs: &mut Vec<Message>,
+        wave_container: &dyn WaveContainer,
+        ui: &mut egui::Ui,
+        start_index: usize,
+        matches: &[VariableMatch],
+    ) {
+        let _ = wave_container;
+        let row_height = ui.text_style_height(&egui::TextStyle::Body);
+        for (i, m) in matches.iter().enumerate() {
+            let row_index = start_index + i;
+            let already_added = self
+                .user
+                .waves
+                .as_ref()
+                .is_some_and(|waves| waves.variables().contains(&m.var));
+            let zebra_background = if row_index % 2 == 0 {
+                self.user.config.theme.variable_list_even_row_background
+            } else {
+                self.user.config.theme.variable_list_odd_row_background
+            };
+
+            let (rect, response) = ui.allocate_exact_size(
+                Vec2::new(ui.available_width(), row_height),
+                egui::Sense::click(),
+            );
+
+            let background = if response.hovered() {
+                self.user.config.theme.variable_list_hovered_row_background
+            } else if already_added {
+                self.user.config.theme.variable_list_added_row_background
+            } else {
+                zebra_background
+            };
+            ui.painter().rect_filled(rect, 0.0, background);
+
+            let job = self.matched_variable_name_layout_job(m);
+            ui.put(rect, egui::Label::new(job).sense(egui::Sense::click()));
+
+            if response.clicked() {
+                msgs.push(Message::AddVariables(vec![m.var.clone()]));
+            }
+        }
+    }
+}
+
+fn matched_name_layout_job(name: &str, indices: &[usize], theme: &SurferTheme) -> LayoutJob {
+    let highlight_format = TextFormat {
+        background: theme.accent_info.background,
+        ..Default::default()
+    };
+
+    let mut job = LayoutJob::default();
+    for (range, highlighted) in highlighted_byte_ranges(name, indices) {
+        let format = if highlighted {
+            highlight_format.clone()
+        } else {
+            TextFormat::default()
+        };
+        job.append(&name[range], 0.0, format);
+    }
+    job
+}
+
+/// Splits `name` into `(byte_range, is_highlighted)` segments from the char indices
+/// reported by the fuzzy matcher, merging adjacent matched characters into one segment.
+fn highlighted_byte_ranges(name: &str, indices: &[usize]) -> Vec<(std::ops::Range<usize>, bool)> {
+    if indices.is_empty() {
+        return vec![(0..name.len(), false)];
+    }
+
+    let highlighted: std::collections::HashSet<usize> = indices.iter().copied().collect();
+    let chars = name.char_indices().collect_vec();
+    let mut segments = vec![];
+    let mut plain_start = 0;
+    let mut i = 0;
+    while i < chars.len() {
+        let (byte_idx, ch) = chars[i];
+        if highlighted.contains(&i) {
+            if byte_idx > plain_start {
+                segments.push((plain_start..byte_idx, false));
+            }
+            let mut end = byte_idx + ch.len_utf8();
+            let mut j = i + 1;
+            while j < chars.len() && highlighted.contains(&j) {
+                end = chars[j].0 + chars[j].1.len_utf8();
+                j += 1;
+            }
+            segments.push((byte_idx..end, true));
+            plain_start = end;
+            i = j;
+        } else {
+            i += 1;
+        }
+    }
+    if plain_start < name.len() {
+        segments.push((plain_start..name.len(), false));
+    }
+    segments
 }
 
 pub fn variable_name_filter_type_menu(
@@ -260,3 +524,278 @@ pub fn variable_name_filter_type_menu(
         });
     }
 }
+
+const STRUCTURAL_FILTER_DIRECTIONS: &[VariableDirection] = &[
+    VariableDirection::Input,
+    VariableDirection::Output,
+    VariableDirection::InOut,
+    VariableDirection::Unknown,
+];
+
+const STRUCTURAL_FILTER_VAR_TYPES: &[VariableType] = &[
+    VariableType::Wire,
+    VariableType::Reg,
+    VariableType::Parameter,
+    VariableType::Integer,
+    VariableType::Real,
+    VariableType::String,
+];
+
+/// Width/direction/type controls for [`StructuralFilter`], e.g. "inputs wider than 8 bits".
+pub fn structural_filter_menu(
+    ui: &mut Ui,
+    msgs: &mut Vec<Message>,
+    structural_filter: &StructuralFilter,
+) {
+    ui.label("Bit width");
+    ui.horizontal(|ui| {
+        let mut min_width = structural_filter.min_width.unwrap_or(0);
+        ui.label("min");
+        if ui
+            .add(
+                egui::DragValue::new(&mut min_width)
+                    .range(0..=u32::MAX)
+                    .custom_formatter(|n, _| if n == 0.0 { "any".into() } else { n.to_string() })
+                    .custom_parser(|s| if s == "any" { Some(0.0) } else { s.parse().ok() }),
+            )
+            .changed()
+        {
+            msgs.push(Message::SetVariableNameFilterMinWidth(
+                (min_width != 0).then_some(min_width),
+            ));
+        }
+        let mut max_width = structural_filter.max_width.unwrap_or(u32::MAX);
+        ui.label("max");
+        if ui
+            .add(
+                egui::DragValue::new(&mut max_width)
+                    .range(0..=u32::MAX)
+                    .custom_formatter(|n, _| {
+                        if n as u32 == u32::MAX {
+                            "any".into()
+                        } else {
+                            n.to_string()
+                        }
+                    })
+                    .custom_parser(|s| {
+                        if s == "any" {
+                            Some(u32::MAX as f64)
+                        } else {
+                            s.parse().ok()
+                        }
+                    }),
+            )
+            .changed()
+        {
+            msgs.push(Message::SetVariableNameFilterMaxWidth(
+                (max_width != u32::MAX).then_some(max_width),
+            ));
+        }
+    });
+    if ui.button("Clear width constraint").clicked() {
+        msgs.push(Message::SetVariableNameFilterMinWidth(None));
+        msgs.push(Message::SetVariableNameFilterMaxWidth(None));
+    }
+
+    ui.separator();
+    ui.label("Direction");
+    for direction in STRUCTURAL_FILTER_DIRECTIONS {
+        let mut checked = structural_filter.directions.contains(direction);
+        if ui
+            .checkbox(&mut checked, format!("{direction:?}"))
+            .changed()
+        {
+            msgs.push(Message::ToggleVariableNameFilterDirection(*direction));
+        }
+    }
+
+    ui.separator();
+    ui.label("Type");
+    for var_type in STRUCTURAL_FILTER_VAR_TYPES {
+        let mut checked = structural_filter.var_types.contains(var_type);
+        if ui
+            .checkbox(&mut checked, format!("{var_type:?}"))
+            .changed()
+        {
+            msgs.push(Message::ToggleVariableNameFilterType(*var_type));
+        }
+    }
+}
+
+/// A [`VariableFilter`] saved under a user-given name for later recall.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VariableFilterPreset {
+    pub name: String,
+    pub filter: VariableFilter,
+}
+
+/// The set of saved filter presets, persisted as part of the serialized user state.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct VariableFilterPresets {
+    pub presets: Vec<VariableFilterPreset>,
+}
+
+impl VariableFilterPresets {
+    pub fn new() -> VariableFilterPresets {
+        VariableFilterPresets::default()
+    }
+
+    pub fn get(&self, name: &str) -> Option<&VariableFilterPreset> {
+        self.presets.iter().find(|p| p.name == name)
+    }
+
+    /// Saves `filter` under `name`, overwriting any existing preset with that name.
+    pub fn save(&mut self, name: String, filter: VariableFilter) {
+        self.presets.retain(|p| p.name != name);
+        self.presets.push(VariableFilterPreset { name, filter });
+    }
+
+    pub fn delete(&mut self, name: &str) {
+        self.presets.retain(|p| p.name != name);
+    }
+}
+
+impl SystemState {
+    pub fn save_variable_filter_preset(&mut self, name: String) {
+        let filter = self.user.variable_filter.clone();
+        self.user.variable_filter_presets.save(name, filter);
+    }
+
+    pub fn apply_variable_filter_preset(&mut self, name: &str) {
+        if let Some(preset) = self.user.variable_filter_presets.get(name) {
+            self.user.variable_filter = preset.filter.clone();
+        }
+    }
+
+    pub fn delete_variable_filter_preset(&mut self, name: &str) {
+        self.user.variable_filter_presets.delete(name);
+    }
+}
+
+/// Saved-preset list with apply/delete buttons, plus a "save current filter as" entry.
+pub fn variable_filter_presets_menu(
+    ui: &mut Ui,
+    msgs: &mut Vec<Message>,
+    presets: &VariableFilterPresets,
+    new_preset_name: &mut String,
+) {
+    ui.label("Saved filters");
+    for preset in &presets.presets {
+        ui.horizontal(|ui| {
+            if ui
+                .button(icons::CHECK_LINE)
+                .on_hover_text("Apply")
+                .clicked()
+            {
+                ui.close_menu();
+                msgs.push(Message::ApplyVariableFilterPreset(preset.name.clone()));
+            }
+            ui.label(&preset.name);
+            if ui
+                .button(icons::DELETE_BIN_LINE)
+                .on_hover_text("Delete")
+                .clicked()
+            {
+                msgs.push(Message::DeleteVariableFilterPreset(preset.name.clone()));
+            }
+        });
+    }
+
+    ui.separator();
+    ui.horizontal(|ui| {
+        ui.add(TextEdit::singleline(new_preset_name).hint_text("Preset name"));
+        ui.add_enabled(!new_preset_name.is_empty(), Button::new("Save as"))
+            .clicked()
+            .then(|| {
+                ui.close_menu();
+                msgs.push(Message::SaveVariableFilterPreset(std::mem::take(
+                    new_preset_name,
+                )));
+            });
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn highlight_ranges_ascii() {
+        let ranges = highlighted_byte_ranges("clkgen", &[0, 1, 2]);
+        assert_eq!(ranges, vec![(0..3, true), (3..6, false)]);
+    }
+
+    #[test]
+    fn highlight_ranges_multibyte() {
+        // "ä" and "ö" are 2 bytes each in UTF-8; char index 0 ("ä") and 2 ("ö") should map
+        // to their actual byte ranges, not their char indices.
+        let name = "äböc";
+        let ranges = highlighted_byte_ranges(name, &[0, 2]);
+        let highlighted: String = ranges
+            .iter()
+            .filter(|(_, hl)| *hl)
+            .map(|(r, _)| &name[r.clone()])
+            .collect();
+        assert_eq!(highlighted, "äö");
+    }
+
+    #[test]
+    fn highlight_ranges_empty_query_is_unhighlighted() {
+        assert_eq!(highlighted_byte_ranges("clk", &[]), vec![(0..3, false)]);
+    }
+
+    #[test]
+    fn structural_filter_is_empty_by_default() {
+        assert!(StructuralFilter::default().is_empty());
+    }
+
+    #[test]
+    fn structural_filter_width_and_direction_and_together() {
+        let filter = StructuralFilter {
+            min_width: Some(8),
+            directions: vec![VariableDirection::Input],
+            ..Default::default()
+        };
+
+        assert!(filter.matches_meta(&StructuralMeta {
+            num_bits: Some(16),
+            direction: Some(VariableDirection::Input),
+            variable_type: None,
+        }));
+        // Wide enough, but wrong direction.
+        assert!(!filter.matches_meta(&StructuralMeta {
+            num_bits: Some(16),
+            direction: Some(VariableDirection::Output),
+            variable_type: None,
+        }));
+        // Right direction, but too narrow.
+        assert!(!filter.matches_meta(&StructuralMeta {
+            num_bits: Some(4),
+            direction: Some(VariableDirection::Input),
+            variable_type: None,
+        }));
+    }
+
+    #[test]
+    fn preset_save_overwrites_same_name() {
+        let mut presets = VariableFilterPresets::new();
+        let mut clocks = VariableFilter::new();
+        clocks.name_filter_str = "clk".into();
+        presets.save("clocks".into(), clocks);
+
+        let mut resets = VariableFilter::new();
+        resets.name_filter_str = "rst".into();
+        presets.save("clocks".into(), resets);
+
+        assert_eq!(presets.presets.len(), 1);
+        assert_eq!(presets.get("clocks").unwrap().filter.name_filter_str, "rst");
+    }
+
+    #[test]
+    fn preset_delete_removes_by_name() {
+        let mut presets = VariableFilterPresets::new();
+        presets.save("clocks".into(), VariableFilter::new());
+        presets.delete("clocks");
+        assert!(presets.get("clocks").is_none());
+    }
+}